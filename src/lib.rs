@@ -1,12 +1,18 @@
 /// Contains functions to get ports that are not being used.
 ///
-/// Supports registered and dynamic ports.
-use std::net::TcpListener;
+/// Supports registered and dynamic ports, over both TCP and UDP.
+use std::net::{IpAddr, Ipv4Addr, TcpListener, UdpSocket};
 
 use lazy_static::lazy_static;
 use rand::seq::SliceRandom;
 use thiserror::Error;
 
+mod port_manager;
+mod reservation;
+
+pub use port_manager::PortManager;
+pub use reservation::{reserve_ports_in_range, ReservationError};
+
 lazy_static! {
 /// The Registered Ports (1024-49151) – which can be used by applications, specific services, and users.
 static ref REGISTERED_PORTS_RANGE: Vec<usize> = (1024..=49151).collect();
@@ -21,8 +27,191 @@ pub enum DynaportError {
   NotEnoughPorts { wanted: usize, got: usize },
 }
 
-fn is_available(port: usize) -> bool {
-  TcpListener::bind(format!("127.0.0.1:{}", port)).is_ok()
+/// The transport protocol a port is probed for.
+///
+/// A port that is free for TCP may still be in use for UDP, so availability is
+/// always checked against a specific protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+  Tcp,
+  Udp,
+}
+
+/// An operator-chosen, inclusive window of ports to allocate within.
+///
+/// Unlike the predefined registered/dynamic ranges, a `Range` lets callers bound
+/// allocation to an arbitrary span — e.g. a test validator carving out
+/// gossip/rpc/repair ports inside a single firewall-friendly window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+  pub min: u16,
+  pub max: u16,
+}
+
+impl Range {
+  /// The ports covered by this range, lowest first.
+  fn ports(&self) -> Vec<usize> {
+    (self.min..=self.max).map(usize::from).collect()
+  }
+}
+
+/// The address all probing binds against unless a caller supplies its own.
+const DEFAULT_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+/// A discovered port whose `TcpListener` is kept alive so the port cannot be
+/// stolen between discovery and use.
+///
+/// The bare-`usize` functions drop their probing listener before returning, leaving
+/// a window in which another process can grab the port. A `PortGuard` instead holds
+/// the live socket and releases it only when dropped. Hand the already-bound socket
+/// straight to the caller with [`PortGuard::into_listener`] to avoid rebinding.
+#[derive(Debug)]
+pub struct PortGuard {
+  port: usize,
+  listener: TcpListener,
+}
+
+impl PortGuard {
+  /// The reserved port.
+  pub fn port(&self) -> usize {
+    self.port
+  }
+
+  /// Consumes the guard and returns the already-bound listener, avoiding any
+  /// re-bind window.
+  pub fn into_listener(self) -> TcpListener {
+    self.listener
+  }
+}
+
+fn is_available(address: IpAddr, protocol: Protocol, port: usize) -> bool {
+  let address = format!("{}:{}", address, port);
+
+  match protocol {
+    Protocol::Tcp => TcpListener::bind(address).is_ok(),
+    Protocol::Udp => UdpSocket::bind(address).is_ok(),
+  }
+}
+
+/// Binds `port` on `address`, returning a guard that owns the live listener.
+fn bind(address: IpAddr, port: usize) -> Option<PortGuard> {
+  TcpListener::bind(format!("{}:{}", address, port))
+    .ok()
+    .map(|listener| PortGuard { port, listener })
+}
+
+/// Binds and returns a guard for a port from `range`, chosen at random.
+fn reserve_random_port(address: IpAddr, range: &[usize]) -> Option<PortGuard> {
+  let mut ports = range.to_vec();
+
+  ports.shuffle(&mut rand::thread_rng());
+
+  ports.into_iter().find_map(|port| bind(address, port))
+}
+
+/// Binds and returns a guard for the lowest port from `range`.
+fn reserve_lowest_port(address: IpAddr, range: &[usize]) -> Option<PortGuard> {
+  range.iter().find_map(|port| bind(address, *port))
+}
+
+/// Binds and returns a guard for the highest port from `range`.
+fn reserve_highest_port(address: IpAddr, range: &[usize]) -> Option<PortGuard> {
+  range.iter().rev().find_map(|port| bind(address, *port))
+}
+
+/// Returns a port from `range` that is not being used, chosen at random.
+fn random_port(address: IpAddr, range: &[usize], protocol: Protocol) -> Option<usize> {
+  let mut ports = range.to_vec();
+
+  ports.shuffle(&mut rand::thread_rng());
+
+  for port in ports {
+    if is_available(address, protocol, port) {
+      return Some(port);
+    }
+  }
+
+  None
+}
+
+/// Returns the lowest port from `range` that is not being used.
+fn lowest_port(address: IpAddr, range: &[usize], protocol: Protocol) -> Option<usize> {
+  for port in range.iter() {
+    if is_available(address, protocol, *port) {
+      return Some(*port);
+    }
+  }
+
+  None
+}
+
+/// Returns the highest port from `range` that is not being used.
+fn highest_port(address: IpAddr, range: &[usize], protocol: Protocol) -> Option<usize> {
+  for port in range.iter().rev() {
+    if is_available(address, protocol, *port) {
+      return Some(*port);
+    }
+  }
+
+  None
+}
+
+/// Returns the `number_of_ports` lowest ports from `range` that aren't being used.
+fn lowest_n_ports(
+  address: IpAddr,
+  range: &[usize],
+  protocol: Protocol,
+  number_of_ports: usize,
+) -> Result<Vec<usize>, DynaportError> {
+  let mut ports = Vec::with_capacity(number_of_ports);
+
+  for port in range.iter() {
+    if ports.len() == number_of_ports {
+      break;
+    }
+
+    if is_available(address, protocol, *port) {
+      ports.push(*port);
+    }
+  }
+
+  if ports.len() == number_of_ports {
+    return Ok(ports);
+  }
+
+  Err(DynaportError::NotEnoughPorts {
+    wanted: number_of_ports,
+    got: ports.len(),
+  })
+}
+
+/// Returns the `number_of_ports` highest ports from `range` that aren't being used.
+fn highest_n_ports(
+  address: IpAddr,
+  range: &[usize],
+  protocol: Protocol,
+  number_of_ports: usize,
+) -> Result<Vec<usize>, DynaportError> {
+  let mut ports = Vec::with_capacity(number_of_ports);
+
+  for port in range.iter().rev() {
+    if ports.len() == number_of_ports {
+      break;
+    }
+
+    if is_available(address, protocol, *port) {
+      ports.push(*port);
+    }
+  }
+
+  if ports.len() == number_of_ports {
+    return Ok(ports);
+  }
+
+  Err(DynaportError::NotEnoughPorts {
+    wanted: number_of_ports,
+    got: ports.len(),
+  })
 }
 
 /// Returns a registered port that is not being used.
@@ -38,17 +227,7 @@ fn is_available(port: usize) -> bool {
 /// }
 /// ```
 pub fn random_registered_port() -> Option<usize> {
-  let mut ports = REGISTERED_PORTS_RANGE.clone();
-
-  ports.shuffle(&mut rand::thread_rng());
-
-  for port in ports {
-    if is_available(port) {
-      return Some(port);
-    }
-  }
-
-  None
+  random_port(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Tcp)
 }
 
 /// Returns the lowest registered port that is not being used.
@@ -62,13 +241,7 @@ pub fn random_registered_port() -> Option<usize> {
 /// }
 /// ```
 pub fn lowest_registered_port() -> Option<usize> {
-  for port in REGISTERED_PORTS_RANGE.iter() {
-    if is_available(*port) {
-      return Some(*port);
-    }
-  }
-
-  None
+  lowest_port(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Tcp)
 }
 
 /// Returns the n lowest registered ports that aren't being used.
@@ -84,22 +257,7 @@ pub fn lowest_registered_port() -> Option<usize> {
 /// }
 /// ```
 pub fn lowest_n_registered_ports(number_of_ports: usize) -> Result<Vec<usize>, DynaportError> {
-  let mut ports = Vec::with_capacity(number_of_ports);
-
-  for port in REGISTERED_PORTS_RANGE.iter() {
-    if ports.len() == number_of_ports {
-      return Ok(ports);
-    }
-
-    if TcpListener::bind(format!("127.0.0.1:{}", port)).is_ok() {
-      ports.push(*port);
-    }
-  }
-
-  Err(DynaportError::NotEnoughPorts {
-    wanted: number_of_ports,
-    got: ports.len(),
-  })
+  lowest_n_ports(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Tcp, number_of_ports)
 }
 
 /// Returns the highest registered port that is not being used.
@@ -113,13 +271,7 @@ pub fn lowest_n_registered_ports(number_of_ports: usize) -> Result<Vec<usize>, D
 /// }
 /// ```
 pub fn highest_registered_port() -> Option<usize> {
-  for port in REGISTERED_PORTS_RANGE.iter().rev() {
-    if is_available(*port) {
-      return Some(*port);
-    }
-  }
-
-  None
+  highest_port(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Tcp)
 }
 
 /// Returns the n highest registered ports that aren't being used.
@@ -135,22 +287,7 @@ pub fn highest_registered_port() -> Option<usize> {
 /// }
 /// ```
 pub fn highest_n_registered_ports(number_of_ports: usize) -> Result<Vec<usize>, DynaportError> {
-  let mut ports = Vec::with_capacity(number_of_ports);
-
-  for port in REGISTERED_PORTS_RANGE.iter().rev() {
-    if ports.len() == number_of_ports {
-      return Ok(ports);
-    }
-
-    if TcpListener::bind(format!("127.0.0.1:{}", port)).is_ok() {
-      ports.push(*port);
-    }
-  }
-
-  Err(DynaportError::NotEnoughPorts {
-    wanted: number_of_ports,
-    got: ports.len(),
-  })
+  highest_n_ports(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Tcp, number_of_ports)
 }
 
 /// Returns a dynamic port that is not being used.
@@ -166,17 +303,7 @@ pub fn highest_n_registered_ports(number_of_ports: usize) -> Result<Vec<usize>,
 /// }
 /// ```
 pub fn random_dynamic_port() -> Option<usize> {
-  let mut ports = DYNAMIC_PORTS_RANGE.clone();
-
-  ports.shuffle(&mut rand::thread_rng());
-
-  for port in ports {
-    if is_available(port) {
-      return Some(port);
-    }
-  }
-
-  None
+  random_port(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Tcp)
 }
 
 /// Returns the lowest dynamic port that is not being used.
@@ -190,13 +317,7 @@ pub fn random_dynamic_port() -> Option<usize> {
 /// }
 /// ```
 pub fn lowest_dynamic_port() -> Option<usize> {
-  for port in DYNAMIC_PORTS_RANGE.iter() {
-    if is_available(*port) {
-      return Some(*port);
-    }
-  }
-
-  None
+  lowest_port(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Tcp)
 }
 
 /// Returns the n lowest dynamic ports that aren't being used.
@@ -212,22 +333,7 @@ pub fn lowest_dynamic_port() -> Option<usize> {
 /// }
 /// ```
 pub fn lowest_n_dynamic_ports(number_of_ports: usize) -> Result<Vec<usize>, DynaportError> {
-  let mut ports = Vec::with_capacity(number_of_ports);
-
-  for port in DYNAMIC_PORTS_RANGE.iter() {
-    if ports.len() == number_of_ports {
-      return Ok(ports);
-    }
-
-    if TcpListener::bind(format!("127.0.0.1:{}", port)).is_ok() {
-      ports.push(*port);
-    }
-  }
-
-  Err(DynaportError::NotEnoughPorts {
-    wanted: number_of_ports,
-    got: ports.len(),
-  })
+  lowest_n_ports(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Tcp, number_of_ports)
 }
 
 /// Returns the highest dynamic port that is not being used.
@@ -241,13 +347,7 @@ pub fn lowest_n_dynamic_ports(number_of_ports: usize) -> Result<Vec<usize>, Dyna
 /// }
 /// ```
 pub fn highest_dynamic_port() -> Option<usize> {
-  for port in DYNAMIC_PORTS_RANGE.iter().rev() {
-    if is_available(*port) {
-      return Some(*port);
-    }
-  }
-
-  None
+  highest_port(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Tcp)
 }
 
 /// Returns the n highest dynamic ports that aren't being used.
@@ -263,22 +363,304 @@ pub fn highest_dynamic_port() -> Option<usize> {
 /// }
 /// ```
 pub fn highest_n_dynamic_ports(number_of_ports: usize) -> Result<Vec<usize>, DynaportError> {
-  let mut ports = Vec::with_capacity(number_of_ports);
+  highest_n_ports(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Tcp, number_of_ports)
+}
 
-  for port in DYNAMIC_PORTS_RANGE.iter().rev() {
-    if ports.len() == number_of_ports {
-      return Ok(ports);
-    }
+/// Returns a registered port that is not being used for UDP.
+///
+/// The port is chosen at random.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::random_registered_udp_port() {
+///   None => println!("no ports available"),
+///   Some(port) => println!("{} is available", port),
+/// }
+/// ```
+pub fn random_registered_udp_port() -> Option<usize> {
+  random_port(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Udp)
+}
 
-    if TcpListener::bind(format!("127.0.0.1:{}", port)).is_ok() {
-      ports.push(*port);
-    }
-  }
+/// Returns the lowest registered port that is not being used for UDP.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::lowest_registered_udp_port() {
+///   None => println!("no ports available"),
+///   Some(port) => println!("{} is available", port),
+/// }
+/// ```
+pub fn lowest_registered_udp_port() -> Option<usize> {
+  lowest_port(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Udp)
+}
 
-  Err(DynaportError::NotEnoughPorts {
-    wanted: number_of_ports,
-    got: ports.len(),
-  })
+/// Returns the n lowest registered ports that aren't being used for UDP.
+///
+/// Returns error if there aren't enough ports available.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::lowest_n_registered_udp_ports(5) {
+///   Err(e) => println!("not enough ports available: {:?}", e),
+///   Ok(ports) => println!("{:?} are available", ports),
+/// }
+/// ```
+pub fn lowest_n_registered_udp_ports(number_of_ports: usize) -> Result<Vec<usize>, DynaportError> {
+  lowest_n_ports(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Udp, number_of_ports)
+}
+
+/// Returns the highest registered port that is not being used for UDP.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::highest_registered_udp_port() {
+///   None => println!("no ports available"),
+///   Some(port) => println!("{} is available", port),
+/// }
+/// ```
+pub fn highest_registered_udp_port() -> Option<usize> {
+  highest_port(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Udp)
+}
+
+/// Returns the n highest registered ports that aren't being used for UDP.
+///
+/// Returns error if there aren't enough ports available.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::highest_n_registered_udp_ports(10) {
+///   Err(e) => println!("not enough ports available: {:?}", e),
+///   Ok(ports) => println!("{:?} are available", ports),
+/// }
+/// ```
+pub fn highest_n_registered_udp_ports(number_of_ports: usize) -> Result<Vec<usize>, DynaportError> {
+  highest_n_ports(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE, Protocol::Udp, number_of_ports)
+}
+
+/// Returns a dynamic port that is not being used for UDP.
+///
+/// The port is chosen at random.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::random_dynamic_udp_port() {
+///   None => println!("no ports available"),
+///   Some(port) => println!("{} is available", port),
+/// }
+/// ```
+pub fn random_dynamic_udp_port() -> Option<usize> {
+  random_port(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Udp)
+}
+
+/// Returns the lowest dynamic port that is not being used for UDP.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::lowest_dynamic_udp_port() {
+///   None => println!("no ports available"),
+///   Some(port) => println!("{} is available", port),
+/// }
+/// ```
+pub fn lowest_dynamic_udp_port() -> Option<usize> {
+  lowest_port(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Udp)
+}
+
+/// Returns the n lowest dynamic ports that aren't being used for UDP.
+///
+/// Returns error if there aren't enough ports available.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::lowest_n_dynamic_udp_ports(3) {
+///   Err(e) => println!("not enough ports available: {:?}", e),
+///   Ok(ports) => println!("{:?} are available", ports),
+/// }
+/// ```
+pub fn lowest_n_dynamic_udp_ports(number_of_ports: usize) -> Result<Vec<usize>, DynaportError> {
+  lowest_n_ports(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Udp, number_of_ports)
+}
+
+/// Returns the highest dynamic port that is not being used for UDP.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::highest_dynamic_udp_port() {
+///   None => println!("no ports available"),
+///   Some(port) => println!("{} is available", port),
+/// }
+/// ```
+pub fn highest_dynamic_udp_port() -> Option<usize> {
+  highest_port(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Udp)
+}
+
+/// Returns the n highest dynamic ports that aren't being used for UDP.
+///
+/// Returns error if there aren't enough ports available.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::highest_n_dynamic_udp_ports(2) {
+///   Err(e) => println!("not enough ports available: {:?}", e),
+///   Ok(ports) => println!("{:?} are available", ports),
+/// }
+/// ```
+pub fn highest_n_dynamic_udp_ports(number_of_ports: usize) -> Result<Vec<usize>, DynaportError> {
+  highest_n_ports(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE, Protocol::Udp, number_of_ports)
+}
+
+/// Returns a port from `range` that is not being used, chosen at random.
+///
+/// # Examples
+///
+/// ```rust
+/// use dynaport::Range;
+///
+/// match dynaport::random_port_in_range(Range { min: 8000, max: 9000 }) {
+///   None => println!("no ports available"),
+///   Some(port) => println!("{} is available", port),
+/// }
+/// ```
+pub fn random_port_in_range(range: Range) -> Option<usize> {
+  random_port_in_range_on(DEFAULT_ADDRESS, range)
+}
+
+/// Returns the lowest port from `range` that is not being used.
+pub fn lowest_port_in_range(range: Range) -> Option<usize> {
+  lowest_port_in_range_on(DEFAULT_ADDRESS, range)
+}
+
+/// Returns the highest port from `range` that is not being used.
+pub fn highest_port_in_range(range: Range) -> Option<usize> {
+  highest_port_in_range_on(DEFAULT_ADDRESS, range)
+}
+
+/// Returns the n lowest ports from `range` that aren't being used.
+///
+/// Returns error if there aren't enough ports available.
+///
+/// # Examples
+///
+/// ```rust
+/// use dynaport::Range;
+///
+/// match dynaport::lowest_n_ports_in_range(Range { min: 8000, max: 9000 }, 3) {
+///   Err(e) => println!("not enough ports available: {:?}", e),
+///   Ok(ports) => println!("{:?} are available", ports),
+/// }
+/// ```
+pub fn lowest_n_ports_in_range(
+  range: Range,
+  number_of_ports: usize,
+) -> Result<Vec<usize>, DynaportError> {
+  lowest_n_ports_in_range_on(DEFAULT_ADDRESS, range, number_of_ports)
+}
+
+/// Returns the n highest ports from `range` that aren't being used.
+///
+/// Returns error if there aren't enough ports available.
+pub fn highest_n_ports_in_range(
+  range: Range,
+  number_of_ports: usize,
+) -> Result<Vec<usize>, DynaportError> {
+  highest_n_ports_in_range_on(DEFAULT_ADDRESS, range, number_of_ports)
+}
+
+/// Returns a port from `range` that is not being used on `address`, chosen at random.
+///
+/// `address` lets the caller probe somewhere other than `127.0.0.1`, e.g. `0.0.0.0`,
+/// a specific interface, or an IPv6 address.
+pub fn random_port_in_range_on(address: IpAddr, range: Range) -> Option<usize> {
+  random_port(address, &range.ports(), Protocol::Tcp)
+}
+
+/// Returns the lowest port from `range` that is not being used on `address`.
+pub fn lowest_port_in_range_on(address: IpAddr, range: Range) -> Option<usize> {
+  lowest_port(address, &range.ports(), Protocol::Tcp)
+}
+
+/// Returns the highest port from `range` that is not being used on `address`.
+pub fn highest_port_in_range_on(address: IpAddr, range: Range) -> Option<usize> {
+  highest_port(address, &range.ports(), Protocol::Tcp)
+}
+
+/// Returns the n lowest ports from `range` that aren't being used on `address`.
+///
+/// Returns error if there aren't enough ports available.
+pub fn lowest_n_ports_in_range_on(
+  address: IpAddr,
+  range: Range,
+  number_of_ports: usize,
+) -> Result<Vec<usize>, DynaportError> {
+  lowest_n_ports(address, &range.ports(), Protocol::Tcp, number_of_ports)
+}
+
+/// Returns the n highest ports from `range` that aren't being used on `address`.
+///
+/// Returns error if there aren't enough ports available.
+pub fn highest_n_ports_in_range_on(
+  address: IpAddr,
+  range: Range,
+  number_of_ports: usize,
+) -> Result<Vec<usize>, DynaportError> {
+  highest_n_ports(address, &range.ports(), Protocol::Tcp, number_of_ports)
+}
+
+/// Reserves a registered port chosen at random, keeping it bound until the guard is dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::reserve_random_registered_port() {
+///   None => println!("no ports available"),
+///   Some(guard) => println!("{} is reserved", guard.port()),
+/// }
+/// ```
+pub fn reserve_random_registered_port() -> Option<PortGuard> {
+  reserve_random_port(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE)
+}
+
+/// Reserves the lowest registered port, keeping it bound until the guard is dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// match dynaport::reserve_lowest_registered_port() {
+///   None => println!("no ports available"),
+///   Some(guard) => println!("{} is reserved", guard.port()),
+/// }
+/// ```
+pub fn reserve_lowest_registered_port() -> Option<PortGuard> {
+  reserve_lowest_port(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE)
+}
+
+/// Reserves the highest registered port, keeping it bound until the guard is dropped.
+pub fn reserve_highest_registered_port() -> Option<PortGuard> {
+  reserve_highest_port(DEFAULT_ADDRESS, &REGISTERED_PORTS_RANGE)
+}
+
+/// Reserves a dynamic port chosen at random, keeping it bound until the guard is dropped.
+pub fn reserve_random_dynamic_port() -> Option<PortGuard> {
+  reserve_random_port(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE)
+}
+
+/// Reserves the lowest dynamic port, keeping it bound until the guard is dropped.
+pub fn reserve_lowest_dynamic_port() -> Option<PortGuard> {
+  reserve_lowest_port(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE)
+}
+
+/// Reserves the highest dynamic port, keeping it bound until the guard is dropped.
+pub fn reserve_highest_dynamic_port() -> Option<PortGuard> {
+  reserve_highest_port(DEFAULT_ADDRESS, &DYNAMIC_PORTS_RANGE)
 }
 
 #[cfg(test)]
@@ -358,4 +740,59 @@ mod tests {
   fn test_highest_n_dynamic_ports() {
     assert_eq!(Ok(vec![65535, 65534, 65533]), highest_n_dynamic_ports(3));
   }
+
+  #[test]
+  fn test_random_registered_udp_port() {
+    assert!(REGISTERED_PORTS_RANGE.contains(&random_registered_udp_port().unwrap()));
+  }
+
+  #[test]
+  fn test_lowest_n_registered_udp_ports() {
+    assert_eq!(Ok(vec![1024, 1025, 1026]), lowest_n_registered_udp_ports(3));
+  }
+
+  #[test]
+  fn test_random_dynamic_udp_port() {
+    assert!(DYNAMIC_PORTS_RANGE.contains(&random_dynamic_udp_port().unwrap()));
+  }
+
+  #[test]
+  fn test_lowest_n_dynamic_udp_ports() {
+    assert_eq!(Ok(vec![49152, 49153, 49154]), lowest_n_dynamic_udp_ports(3));
+  }
+
+  #[test]
+  fn test_random_port_in_range() {
+    let range = Range { min: 8000, max: 9000 };
+
+    let port = random_port_in_range(range).unwrap();
+
+    assert!((8000..=9000).contains(&port));
+  }
+
+  #[test]
+  fn test_lowest_n_ports_in_range() {
+    let range = Range { min: 8000, max: 9000 };
+
+    assert_eq!(Ok(vec![8000, 8001, 8002]), lowest_n_ports_in_range(range, 3));
+  }
+
+  #[test]
+  fn test_reserve_lowest_registered_port_keeps_the_port_bound() {
+    let guard = reserve_lowest_registered_port().unwrap();
+
+    assert!(REGISTERED_PORTS_RANGE.contains(&guard.port()));
+    // The guard owns the listener, so the same port can no longer be bound.
+    assert!(TcpListener::bind(format!("127.0.0.1:{}", guard.port())).is_err());
+  }
+
+  #[test]
+  fn test_port_guard_into_listener() {
+    let guard = reserve_lowest_dynamic_port().unwrap();
+    let port = guard.port();
+
+    let listener = guard.into_listener();
+
+    assert_eq!(port, listener.local_addr().unwrap().port() as usize);
+  }
 }
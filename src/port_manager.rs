@@ -0,0 +1,123 @@
+/// A stateful allocator that never hands out the same port twice.
+///
+/// The free functions are one-shot: nothing remembers what they returned, so a
+/// program needing several ports in quick succession can get duplicates. A
+/// `PortManager` remembers every port it has handed out and skips those on the next
+/// allocation, verifying real availability before returning. `release` puts a port
+/// back so it can be handed out again.
+use std::collections::HashSet;
+
+use crate::{is_available, DynaportError, Protocol, Range, DEFAULT_ADDRESS};
+
+/// Allocates distinct, available ports from a fixed range.
+#[derive(Debug)]
+pub struct PortManager {
+  range: Range,
+  taken: HashSet<u16>,
+}
+
+impl PortManager {
+  /// Creates a manager that allocates within `range`.
+  pub fn new(range: Range) -> Self {
+    Self {
+      range,
+      taken: HashSet::new(),
+    }
+  }
+
+  /// Allocates a single available port, or `None` if the range is exhausted.
+  ///
+  /// Ports already handed out are skipped, and the candidate is verified to be free
+  /// before being returned.
+  pub fn allocate(&mut self) -> Option<usize> {
+    for port in self.range.min..=self.range.max {
+      if self.taken.contains(&port) {
+        continue;
+      }
+
+      if is_available(DEFAULT_ADDRESS, Protocol::Tcp, usize::from(port)) {
+        self.taken.insert(port);
+        return Some(usize::from(port));
+      }
+    }
+
+    None
+  }
+
+  /// Allocates `number_of_ports` distinct available ports.
+  ///
+  /// Returns error if there aren't enough ports available; on failure nothing is
+  /// recorded as taken.
+  pub fn allocate_n(&mut self, number_of_ports: usize) -> Result<Vec<usize>, DynaportError> {
+    let mut allocated = Vec::with_capacity(number_of_ports);
+
+    for port in self.range.min..=self.range.max {
+      if allocated.len() == number_of_ports {
+        break;
+      }
+
+      if self.taken.contains(&port) {
+        continue;
+      }
+
+      if is_available(DEFAULT_ADDRESS, Protocol::Tcp, usize::from(port)) {
+        allocated.push(port);
+      }
+    }
+
+    if allocated.len() != number_of_ports {
+      return Err(DynaportError::NotEnoughPorts {
+        wanted: number_of_ports,
+        got: allocated.len(),
+      });
+    }
+
+    self.taken.extend(allocated.iter().copied());
+
+    Ok(allocated.into_iter().map(usize::from).collect())
+  }
+
+  /// Returns `port` to the pool so it can be allocated again.
+  pub fn release(&mut self, port: usize) {
+    if let Ok(port) = u16::try_from(port) {
+      self.taken.remove(&port);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn allocate_does_not_repeat_ports() {
+    let mut manager = PortManager::new(Range { min: 7000, max: 7100 });
+
+    let first = manager.allocate().unwrap();
+    let second = manager.allocate().unwrap();
+
+    assert_ne!(first, second);
+  }
+
+  #[test]
+  fn allocate_n_returns_distinct_ports() {
+    let mut manager = PortManager::new(Range { min: 7200, max: 7300 });
+
+    let ports = manager.allocate_n(3).unwrap();
+
+    let unique: HashSet<_> = ports.iter().copied().collect();
+    assert_eq!(3, unique.len());
+  }
+
+  #[test]
+  fn released_port_can_be_allocated_again() {
+    let mut manager = PortManager::new(Range { min: 7400, max: 7400 });
+
+    let port = manager.allocate().unwrap();
+    assert_eq!(None, manager.allocate());
+
+    manager.release(port);
+
+    assert_eq!(Some(port), manager.allocate());
+  }
+}
@@ -0,0 +1,255 @@
+/// Cross-process, persistent port reservation with expiry.
+///
+/// Plain probing (`lowest_n_registered_ports` and friends) has a time-of-check to
+/// time-of-use gap: two processes can both see the same port as free and then
+/// collide when they bind it. The reservation subsystem closes that gap by
+/// recording allocations in a shared on-disk file guarded by an exclusive OS file
+/// lock, so independent processes sharing a machine allocate deterministically and
+/// without conflict.
+///
+/// Reservations carry a TTL. Expired entries are ignored and overwritten, so a
+/// process that crashes while holding ports self-heals once its reservation lapses.
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Range;
+
+#[derive(Debug, Error)]
+pub enum ReservationError {
+  #[error("io error while accessing the reservation file: {0}")]
+  Io(#[from] std::io::Error),
+
+  #[error("failed to read or write the reservation file: {0}")]
+  Serde(#[from] serde_json::Error),
+
+  #[error("could not find {wanted:?} free contiguous ports in the range")]
+  NoContiguousBlock { wanted: usize },
+
+  #[error("the system clock is set before the unix epoch")]
+  ClockBeforeEpoch,
+}
+
+/// A single recorded reservation: a contiguous block of `size` ports starting at
+/// the key it is stored under, valid until `expires` (seconds since the unix epoch).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Entry {
+  size: u16,
+  expires: u64,
+}
+
+/// The on-disk document shared by every process reserving within a range.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Document {
+  /// Where the next allocation scan starts; advances past each block handed out.
+  next: u16,
+  /// Starting-port → reservation.
+  reservations: HashMap<u16, Entry>,
+}
+
+fn now_secs() -> Result<u64, ReservationError> {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|elapsed| elapsed.as_secs())
+    .map_err(|_| ReservationError::ClockBeforeEpoch)
+}
+
+/// Returns `true` if the blocks `[start, start + size)` and `[other_start, other_start + other_size)` overlap.
+fn overlaps(start: u16, size: u16, other_start: u16, other_size: u16) -> bool {
+  let end = start.saturating_add(size.saturating_sub(1));
+  let other_end = other_start.saturating_add(other_size.saturating_sub(1));
+
+  start <= other_end && other_start <= end
+}
+
+/// Reserves `number_of_ports` contiguous ports within `range`, recording the
+/// allocation in the file at `path` for `ttl`.
+///
+/// The whole operation runs under an exclusive lock on the file, so concurrent
+/// callers — in this process or another — never hand out overlapping blocks. Each
+/// candidate block is additionally verified to be bindable via `TcpListener::bind`
+/// before being returned.
+///
+/// Returns [`ReservationError::NoContiguousBlock`] if no free block large enough
+/// exists in the range.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use dynaport::Range;
+///
+/// let ports = dynaport::reserve_ports_in_range(
+///   "/tmp/dynaport.json",
+///   Range { min: 8000, max: 9000 },
+///   3,
+///   Duration::from_secs(60),
+/// )
+/// .unwrap();
+/// println!("reserved {:?}", ports);
+/// ```
+pub fn reserve_ports_in_range(
+  path: impl AsRef<Path>,
+  range: Range,
+  number_of_ports: usize,
+  ttl: Duration,
+) -> Result<Vec<usize>, ReservationError> {
+  let mut file = OpenOptions::new()
+    .read(true)
+    .write(true)
+    .create(true)
+    .truncate(false)
+    .open(path)?;
+
+  file.lock_exclusive()?;
+
+  // The lock is released when `file` is dropped; run the rest in a helper function
+  // so an early return from it still reaches the unlock below.
+  let result = reserve_locked(&mut file, range, number_of_ports, ttl);
+
+  // Best-effort unlock; dropping the file would do this anyway.
+  let _ = FileExt::unlock(&file);
+
+  result
+}
+
+fn reserve_locked(
+  file: &mut std::fs::File,
+  range: Range,
+  number_of_ports: usize,
+  ttl: Duration,
+) -> Result<Vec<usize>, ReservationError> {
+  // Invalid input has no valid block by definition; signal it the same way an
+  // exhausted range does rather than underflowing the span computation below.
+  if range.min > range.max || number_of_ports == 0 {
+    return Err(ReservationError::NoContiguousBlock {
+      wanted: number_of_ports,
+    });
+  }
+
+  let mut contents = String::new();
+  file.read_to_string(&mut contents)?;
+
+  let mut document: Document = if contents.trim().is_empty() {
+    Document {
+      next: range.min,
+      ..Document::default()
+    }
+  } else {
+    serde_json::from_str(&contents)?
+  };
+
+  // A cursor left over from a different range, or a fresh zeroed document, is
+  // pulled back inside the current range.
+  if document.next < range.min || document.next > range.max {
+    document.next = range.min;
+  }
+
+  let now = now_secs()?;
+
+  // Drop lapsed reservations so the file does not grow without bound.
+  document
+    .reservations
+    .retain(|_, entry| entry.expires > now);
+
+  let size = number_of_ports as u16;
+  let span = (range.max - range.min) as usize + 1;
+
+  let mut start = document.next;
+
+  for _ in 0..span {
+    let fits = start as usize + number_of_ports - 1 <= range.max as usize;
+
+    let free = fits
+      && !document
+        .reservations
+        .iter()
+        .any(|(reserved_start, entry)| overlaps(start, size, *reserved_start, entry.size));
+
+    if free && (start..=start + (size - 1)).all(is_bindable) {
+      document.reservations.insert(
+        start,
+        Entry {
+          size,
+          expires: now + ttl.as_secs(),
+        },
+      );
+
+      document.next = match start.checked_add(size) {
+        Some(next) if next <= range.max => next,
+        _ => range.min,
+      };
+
+      write_document(file, &document)?;
+
+      return Ok((start..=start + (size - 1)).map(usize::from).collect());
+    }
+
+    start = if start >= range.max {
+      range.min
+    } else {
+      start + 1
+    };
+  }
+
+  Err(ReservationError::NoContiguousBlock {
+    wanted: number_of_ports,
+  })
+}
+
+fn is_bindable(port: u16) -> bool {
+  TcpListener::bind(format!("127.0.0.1:{}", port)).is_ok()
+}
+
+fn write_document(file: &mut std::fs::File, document: &Document) -> Result<(), ReservationError> {
+  let serialized = serde_json::to_string(document)?;
+
+  file.seek(SeekFrom::Start(0))?;
+  file.set_len(0)?;
+  file.write_all(serialized.as_bytes())?;
+  file.flush()?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_path(name: &str) -> std::path::PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!("dynaport_{}_{}.json", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    path
+  }
+
+  #[test]
+  fn reserves_contiguous_ports_from_the_range_minimum() {
+    let path = temp_path("contiguous");
+    let range = Range { min: 9100, max: 9200 };
+
+    let ports =
+      reserve_ports_in_range(&path, range, 3, Duration::from_secs(60)).unwrap();
+
+    assert_eq!(vec![9100, 9101, 9102], ports);
+  }
+
+  #[test]
+  fn does_not_hand_out_a_reserved_block_twice() {
+    let path = temp_path("no_double");
+    let range = Range { min: 9300, max: 9400 };
+
+    let first = reserve_ports_in_range(&path, range, 2, Duration::from_secs(60)).unwrap();
+    let second = reserve_ports_in_range(&path, range, 2, Duration::from_secs(60)).unwrap();
+
+    assert_eq!(vec![9300, 9301], first);
+    assert_eq!(vec![9302, 9303], second);
+  }
+}